@@ -0,0 +1,239 @@
+//! `async` support, for sharing a bus between tasks on an async executor (embassy, RTIC, ...)
+//! without busy-spinning or blocking the whole executor while the bus is held.
+//!
+//! Everything in this module is gated behind the `async` cargo feature. The module is named
+//! `asynch` because `async` is a reserved keyword.
+
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::mutex::Mutex;
+use embedded_hal_1::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::spi::SpiBus;
+
+/// An async counterpart to [`BusMutex`][crate::BusMutex].
+///
+/// Where [`BusMutex::lock`][crate::BusMutex::lock] runs a synchronous closure while holding the
+/// bus, `AsyncBusMutex::lock` runs a closure that returns a future, awaiting it while holding the
+/// lock.  A task contending for an already-locked bus `.await`s here and yields back to the
+/// executor instead of blocking it.
+#[allow(async_fn_in_trait)]
+pub trait AsyncBusMutex {
+    /// The underlying bus type protected by this mutex.
+    type Bus;
+
+    /// Lock the mutex and run the async closure `f` with exclusive access to the bus.
+    async fn lock<R>(&self, f: impl AsyncFnOnce(&mut Self::Bus) -> R) -> R;
+}
+
+/// An async mutex based on an `embassy-sync` [`Mutex`].
+///
+/// Generic over the [`RawMutex`] implementation, so the same type works whether the bus is
+/// shared within a single priority level (`NoopRawMutex`), across interrupts
+/// (`CriticalSectionRawMutex`), or across executor threads (`ThreadModeRawMutex`).
+pub struct AsyncMutex<Raw: RawMutex, BUS> {
+    bus: Mutex<Raw, BUS>,
+}
+
+impl<Raw: RawMutex, BUS> AsyncMutex<Raw, BUS> {
+    /// Create a new `AsyncMutex`, wrapping the passed bus.
+    pub fn create(bus: BUS) -> Self {
+        AsyncMutex {
+            bus: Mutex::new(bus),
+        }
+    }
+}
+
+impl<Raw: RawMutex, BUS> AsyncBusMutex for AsyncMutex<Raw, BUS> {
+    type Bus = BUS;
+
+    async fn lock<R>(&self, f: impl AsyncFnOnce(&mut BUS) -> R) -> R {
+        let mut bus = self.bus.lock().await;
+        f(&mut bus).await
+    }
+}
+
+/// Proxy type for I2C bus sharing between async tasks.
+///
+/// Implements `embedded-hal-async`'s [`I2c`][embedded_hal_async::i2c::I2c] trait so it can be
+/// passed to async drivers instead of the bus instance, analogous to
+/// [`I2cProxy`][crate::I2cProxy] for blocking drivers.
+///
+/// An `I2cProxy` is created by calling [`AsyncBusManager::acquire_i2c()`][acquire_i2c].
+///
+/// [acquire_i2c]: ./struct.AsyncBusManager.html#method.acquire_i2c
+pub struct I2cProxy<'a, M> {
+    mutex: &'a M,
+}
+
+impl<'a, M> Clone for I2cProxy<'a, M> {
+    fn clone(&self) -> Self {
+        Self { mutex: self.mutex }
+    }
+}
+
+impl<'a, M> embedded_hal_async::i2c::ErrorType for I2cProxy<'a, M>
+where
+    M: AsyncBusMutex,
+    M::Bus: embedded_hal_async::i2c::ErrorType,
+{
+    type Error = <M::Bus as embedded_hal_async::i2c::ErrorType>::Error;
+}
+
+impl<'a, M> embedded_hal_async::i2c::I2c for I2cProxy<'a, M>
+where
+    M: AsyncBusMutex,
+    M::Bus: embedded_hal_async::i2c::I2c,
+{
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal_async::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.mutex
+            .lock(async move |bus: &mut M::Bus| bus.transaction(address, operations).await)
+            .await
+    }
+}
+
+/// Proxy type for SPI bus sharing between async tasks, managing its own chip-select pin.
+///
+/// Mirrors [`SpiDeviceProxy`][crate::SpiDeviceProxy]: it owns the chip-select pin and wraps an
+/// underlying [`SpiBus`][embedded_hal_async::spi::SpiBus], asserting and deasserting CS itself as
+/// part of a single locked transaction, rather than forwarding to an already CS-complete
+/// `SpiDevice`. This is what lets several CS-selected peripherals share one SPI bus across tasks.
+///
+/// It also owns a `delay`, used to honor [`Operation::DelayNs`][Op::DelayNs] as
+/// `embedded-hal-async`'s `SpiDevice` contract requires, the way `embedded-hal-bus`'s
+/// `ExclusiveDevice` does.
+///
+/// An `SpiProxy` is created by calling
+/// [`AsyncBusManager::acquire_spi_device()`][acquire_spi_device].
+///
+/// [Op::DelayNs]: embedded_hal_async::spi::Operation::DelayNs
+/// [acquire_spi_device]: ./struct.AsyncBusManager.html#method.acquire_spi_device
+pub struct SpiProxy<'a, M, CS, D> {
+    mutex: &'a M,
+    cs: CS,
+    delay: D,
+}
+
+impl<'a, M, CS, D> SpiProxy<'a, M, CS, D>
+where
+    M: AsyncBusMutex,
+    CS: OutputPin,
+{
+    /// Run `f` as a single SPI transaction: assert CS, run `f` against the locked bus, then
+    /// deassert CS, all while holding the bus mutex so no other proxy can interleave.
+    pub async fn with_cs<R, E>(
+        &mut self,
+        f: impl AsyncFnOnce(&mut M::Bus) -> Result<R, E>,
+    ) -> Result<R, crate::SpiDeviceError<E, CS::Error>> {
+        let cs = &mut self.cs;
+        self.mutex
+            .lock(async move |bus: &mut M::Bus| {
+                cs.set_low().map_err(crate::SpiDeviceError::Cs)?;
+                let result = f(bus).await.map_err(crate::SpiDeviceError::Spi);
+                cs.set_high().map_err(crate::SpiDeviceError::Cs)?;
+                result
+            })
+            .await
+    }
+}
+
+impl<'a, M, CS, D> embedded_hal_async::spi::ErrorType for SpiProxy<'a, M, CS, D>
+where
+    M: AsyncBusMutex,
+    M::Bus: embedded_hal_async::spi::ErrorType,
+    CS: OutputPin,
+{
+    type Error =
+        crate::SpiDeviceError<<M::Bus as embedded_hal_async::spi::ErrorType>::Error, CS::Error>;
+}
+
+impl<'a, M, CS, D> embedded_hal_async::spi::SpiDevice for SpiProxy<'a, M, CS, D>
+where
+    M: AsyncBusMutex,
+    M::Bus: embedded_hal_async::spi::SpiBus,
+    CS: OutputPin,
+    D: DelayNs,
+{
+    async fn transaction(
+        &mut self,
+        operations: &mut [embedded_hal_async::spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let mutex = self.mutex;
+        let cs = &mut self.cs;
+        let delay = &mut self.delay;
+        mutex
+            .lock(async move |bus: &mut M::Bus| {
+                cs.set_low().map_err(crate::SpiDeviceError::Cs)?;
+                let result = (async {
+                    for operation in operations {
+                        match operation {
+                            embedded_hal_async::spi::Operation::Read(buffer) => {
+                                bus.read(buffer).await?
+                            }
+                            embedded_hal_async::spi::Operation::Write(buffer) => {
+                                bus.write(buffer).await?
+                            }
+                            embedded_hal_async::spi::Operation::Transfer(read, write) => {
+                                bus.transfer(read, write).await?
+                            }
+                            embedded_hal_async::spi::Operation::TransferInPlace(buffer) => {
+                                bus.transfer_in_place(buffer).await?
+                            }
+                            embedded_hal_async::spi::Operation::DelayNs(ns) => {
+                                // Flush so the delay actually falls between transfers instead of
+                                // racing a still-pending one.
+                                bus.flush().await?;
+                                delay.delay_ns(*ns).await;
+                            }
+                        }
+                    }
+                    bus.flush().await
+                })
+                .await
+                .map_err(crate::SpiDeviceError::Spi);
+                cs.set_high().map_err(crate::SpiDeviceError::Cs)?;
+                result
+            })
+            .await
+    }
+}
+
+/// The async counterpart to [`BusManager`][crate::BusManager].
+///
+/// Hands out [`I2cProxy`]/[`SpiProxy`] proxies backed by an [`AsyncBusMutex`], for sharing a bus
+/// between async tasks.
+pub struct AsyncBusManager<M> {
+    mutex: M,
+}
+
+/// [`AsyncBusManager`] that uses an [`AsyncMutex`] for synchronization.
+pub type BusManagerAsync<Raw, BUS> = AsyncBusManager<AsyncMutex<Raw, BUS>>;
+
+impl<Raw: RawMutex, BUS> BusManagerAsync<Raw, BUS> {
+    /// Create a new `BusManagerAsync`, wrapping the passed bus.
+    pub fn new(bus: BUS) -> Self {
+        AsyncBusManager {
+            mutex: AsyncMutex::create(bus),
+        }
+    }
+}
+
+impl<M: AsyncBusMutex> AsyncBusManager<M> {
+    /// Acquire an [`I2cProxy`] for this bus.
+    pub fn acquire_i2c(&self) -> I2cProxy<'_, M> {
+        I2cProxy { mutex: &self.mutex }
+    }
+
+    /// Acquire an [`SpiProxy`] for this bus, managing the given chip-select pin and using `delay`
+    /// to honor `Operation::DelayNs`.
+    pub fn acquire_spi_device<CS, D>(&self, cs: CS, delay: D) -> SpiProxy<'_, M, CS, D> {
+        SpiProxy {
+            mutex: &self.mutex,
+            cs,
+            delay,
+        }
+    }
+}
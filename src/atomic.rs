@@ -0,0 +1,149 @@
+//! Atomic, non-blocking mutex for sharing a bus across interrupt priority levels without a
+//! critical section.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Returned by [`AtomicMutex::try_lock`] when the bus is already locked by another accessor.
+#[derive(Debug)]
+pub struct Busy;
+
+/// Error type returned by proxies backed by an [`AtomicMutex`].
+///
+/// Because [`AtomicMutex::try_lock`] can fail without ever touching the bus, proxies using it
+/// cannot simply forward the underlying bus's error type the way the other proxies do; this enum
+/// adds the extra [`Busy`][AtomicError::Busy] case on top of the wrapped bus error.
+#[derive(Debug)]
+pub enum AtomicError<E> {
+    /// The bus was already locked by another accessor; the operation was not attempted.
+    Busy,
+    /// The underlying bus returned an error.
+    Other(E),
+}
+
+/// A mutex based on an `AtomicBool` flag.
+///
+/// Unlike [`CortexMMutex`][crate::CortexMMutex] or [`StdMutex`][crate::StdMutex], this mutex
+/// never blocks to acquire the bus: if it is already locked, [`try_lock`][AtomicMutex::try_lock]
+/// returns [`Busy`] immediately instead of disabling interrupts or parking the calling thread.
+/// This makes it safe to share a bus between, say, an interrupt handler and the main loop
+/// without risking a deadlock or holding off a higher interrupt priority level.
+///
+/// Modelled on `embedded-hal-bus`'s `AtomicDevice`.
+pub struct AtomicMutex<BUS> {
+    bus: UnsafeCell<BUS>,
+    locked: AtomicBool,
+}
+
+// SAFETY: `try_lock` only ever hands out a `&mut BUS` to the thread that won the
+// compare-exchange on `locked`, so concurrent access from multiple contexts is synchronized
+// through the atomic flag even though `UnsafeCell` is not `Sync` on its own.
+unsafe impl<BUS> Sync for AtomicMutex<BUS> where BUS: Send {}
+
+impl<BUS> AtomicMutex<BUS> {
+    /// Create a new `AtomicMutex`, wrapping the passed bus.
+    pub fn create(bus: BUS) -> Self {
+        AtomicMutex {
+            bus: UnsafeCell::new(bus),
+            locked: AtomicBool::new(false),
+        }
+    }
+
+    /// Attempt to lock the bus and run `f` with exclusive access to it.
+    ///
+    /// Returns [`Busy`] instead of blocking if the bus is already locked by another accessor.
+    pub fn try_lock<R>(&self, f: impl FnOnce(&mut BUS) -> R) -> Result<R, Busy> {
+        if self
+            .locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(Busy);
+        }
+
+        struct ReleaseGuard<'a>(&'a AtomicBool);
+
+        impl Drop for ReleaseGuard<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, Ordering::Release);
+            }
+        }
+
+        let _guard = ReleaseGuard(&self.locked);
+
+        // SAFETY: the compare-exchange above established that we are the only holder of the
+        // lock; `_guard` releases it on drop (including when `f` panics), so the exclusive
+        // borrow below never outlives our hold on `locked`.
+        let bus = unsafe { &mut *self.bus.get() };
+        Ok(f(bus))
+    }
+}
+
+/// [`BusManager`][crate::BusManager] that uses an [`AtomicMutex`] for synchronization.
+///
+/// Unlike [`BusManagerCortexM`][crate::BusManagerCortexM], this manager's proxies never block:
+/// contending accesses fail fast with [`AtomicError::Busy`] instead of waiting inside a critical
+/// section.
+pub type BusManagerAtomic<BUS> = crate::BusManager<AtomicMutex<BUS>>;
+
+impl<BUS> BusManagerAtomic<BUS> {
+    /// Create a new `BusManagerAtomic`, wrapping the passed bus.
+    pub fn new(bus: BUS) -> Self {
+        crate::BusManager {
+            mutex: AtomicMutex::create(bus),
+        }
+    }
+
+    /// Acquire an [`I2cProxy`][crate::I2cProxy] for this bus.
+    pub fn acquire_i2c(&self) -> crate::I2cProxy<'_, AtomicMutex<BUS>> {
+        crate::I2cProxy { mutex: &self.mutex }
+    }
+
+    /// Acquire an [`SpiDeviceProxy`][crate::SpiDeviceProxy] for this bus, managing the given
+    /// chip-select pin.
+    ///
+    /// There is deliberately no plain `acquire_spi()` here: [`SpiProxy`][crate::SpiProxy] toggles
+    /// CS outside the lock, which would reintroduce a CS race across the very interrupt
+    /// priority levels this mutex exists to share safely between. `SpiDeviceProxy` asserts and
+    /// deasserts CS itself as part of the locked transaction, so it stays safe under contention.
+    pub fn acquire_spi_device<CS>(&self, cs: CS) -> crate::SpiDeviceProxy<'_, AtomicMutex<BUS>, CS> {
+        crate::SpiDeviceProxy {
+            mutex: &self.mutex,
+            cs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_lock_succeeds_when_uncontended() {
+        let mutex = AtomicMutex::create(0u8);
+        let result = mutex.try_lock(|bus| {
+            *bus += 1;
+            *bus
+        });
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn try_lock_returns_busy_on_contention() {
+        let mutex = AtomicMutex::create(0u8);
+        let nested = mutex.try_lock(|_| mutex.try_lock(|_| ()));
+        assert!(matches!(nested, Ok(Err(Busy))));
+    }
+
+    #[test]
+    fn guard_releases_lock_even_if_closure_panics() {
+        let mutex = AtomicMutex::create(0u8);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            mutex.try_lock(|_| panic!("boom"))
+        }));
+        assert!(result.is_err());
+
+        // The drop guard must have released the lock despite the panic unwinding through it.
+        assert!(mutex.try_lock(|_| ()).is_ok());
+    }
+}
@@ -0,0 +1,47 @@
+//! Cortex-M specific mutex, using a critical section to synchronize bus access across interrupt
+//! priority levels.
+
+/// A mutex based on a `cortex_m::interrupt::Mutex`.
+///
+/// This mutex can be used to share a bus between different interrupt priority levels on Cortex-M
+/// microcontrollers.  Locking disables interrupts for the duration of the access, so contending
+/// contexts always wait rather than observing a busy bus.
+pub struct CortexMMutex<BUS> {
+    bus: cortex_m::interrupt::Mutex<core::cell::RefCell<BUS>>,
+}
+
+impl<BUS> CortexMMutex<BUS> {
+    /// Create a new `CortexMMutex`, wrapping the passed bus.
+    pub fn create(bus: BUS) -> Self {
+        CortexMMutex {
+            bus: cortex_m::interrupt::Mutex::new(core::cell::RefCell::new(bus)),
+        }
+    }
+}
+
+impl<BUS> crate::BusMutex for CortexMMutex<BUS> {
+    type Bus = BUS;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut BUS) -> R) -> R {
+        cortex_m::interrupt::free(|cs| {
+            let mut bus = self.bus.borrow(cs).borrow_mut();
+            f(&mut bus)
+        })
+    }
+}
+
+/// [`BusManager`][crate::BusManager] that uses a [`CortexMMutex`] for synchronization.
+///
+/// This manager can be shared between the main loop and interrupt handlers, as long as they all
+/// run at priority levels managed by a single critical section (see [`cortex_m::interrupt`] for
+/// details).
+pub type BusManagerCortexM<BUS> = crate::BusManager<CortexMMutex<BUS>>;
+
+impl<BUS> BusManagerCortexM<BUS> {
+    /// Create a new `BusManagerCortexM`, wrapping the passed bus.
+    pub fn new(bus: BUS) -> Self {
+        crate::BusManager {
+            mutex: CortexMMutex::create(bus),
+        }
+    }
+}
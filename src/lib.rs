@@ -0,0 +1,119 @@
+//! # `shared-bus`
+//!
+//! This crate allows you to share a single bus (I2C, SPI, ADC, ...) between multiple peripherals,
+//! including peripherals that live in different execution contexts (threads, interrupt handlers,
+//! ...).  Instead of giving ownership of the bus to a single driver, you create a [`BusManager`]
+//! which owns the bus, and hand each driver an [`I2cProxy`]/[`SpiProxy`]/... acquired from the
+//! manager.  Each proxy implements the relevant `embedded-hal` traits, so it can be used as a
+//! drop-in replacement for the bus itself.  SPI drivers that need to be shared across tasks or
+//! threads (rather than just within one) should use [`SpiDeviceProxy`] instead of [`SpiProxy`].
+//!
+//! Synchronization between proxies is provided by a [`BusMutex`] implementation.  Which one to
+//! use depends on what the proxies need to be shared across:
+//!
+//! * [`NullMutex`] / [`BusManagerSimple`] for sharing within a single execution context.
+//! * [`StdMutex`] / [`BusManagerStd`] for sharing across threads on platforms with `std`.
+//! * [`CortexMMutex`] / [`BusManagerCortexM`] for sharing across interrupt priority levels on
+//!   Cortex-M microcontrollers.
+//!
+//! With the `async` feature enabled, the [`asynch`] module provides an async counterpart built on
+//! `embedded-hal-async`, for sharing a bus between tasks on an async executor.
+#![cfg_attr(not(test), no_std)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod mutex;
+pub use mutex::{BusMutex, NullMutex};
+
+mod atomic;
+pub use atomic::{AtomicError, AtomicMutex, Busy, BusManagerAtomic};
+
+pub mod proxies;
+pub use proxies::{
+    AdcProxy, CanProxy, I2cProxy, I2cTransactionError, Operation, SpiDeviceError, SpiDeviceProxy,
+    SpiProxy,
+};
+
+#[cfg(feature = "cortex-m")]
+mod cortexm;
+#[cfg(feature = "cortex-m")]
+pub use cortexm::{BusManagerCortexM, CortexMMutex};
+
+#[cfg(feature = "std")]
+#[path = "std.rs"]
+mod std_mutex;
+#[cfg(feature = "std")]
+pub use std_mutex::{BusManagerStd, StdMutex};
+
+#[cfg(feature = "async")]
+pub mod asynch;
+#[cfg(feature = "async")]
+pub use asynch::{AsyncBusManager, AsyncBusMutex, AsyncMutex, BusManagerAsync};
+#[cfg(feature = "async")]
+pub use asynch::{I2cProxy as AsyncI2cProxy, SpiProxy as AsyncSpiProxy};
+
+/// The main entry point of this crate.
+///
+/// A `BusManager` owns the shared bus (through a [`BusMutex`] implementation `M`) and hands out
+/// proxies that can be passed to drivers instead of the bus itself.  Which concrete `BusManager`
+/// to use depends on the sharing scenario; see the [crate] documentation for an overview.
+pub struct BusManager<M> {
+    pub(crate) mutex: M,
+}
+
+/// [`BusManager`] that uses a [`NullMutex`] for synchronization.
+///
+/// This manager (and the proxies it creates) can only be used within a single execution context
+/// (see [`NullMutex`] for details).
+pub type BusManagerSimple<BUS> = BusManager<NullMutex<BUS>>;
+
+impl<BUS> BusManagerSimple<BUS> {
+    /// Create a new `BusManagerSimple`, wrapping the passed bus.
+    pub fn new(bus: BUS) -> Self {
+        BusManager {
+            mutex: NullMutex::create(bus),
+        }
+    }
+
+    /// Acquire an [`SpiProxy`] for this bus.
+    ///
+    /// This is only available on `BusManagerSimple` because `SpiProxy` is `!Send`; see its
+    /// documentation for details.
+    pub fn acquire_spi(&self) -> SpiProxy<'_, NullMutex<BUS>> {
+        SpiProxy {
+            mutex: &self.mutex,
+            _u: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<M: BusMutex> BusManager<M> {
+    /// Acquire an [`I2cProxy`] for this bus.
+    pub fn acquire_i2c(&self) -> I2cProxy<'_, M> {
+        I2cProxy { mutex: &self.mutex }
+    }
+
+    /// Acquire an [`AdcProxy`] for this bus.
+    pub fn acquire_adc(&self) -> AdcProxy<'_, M> {
+        AdcProxy { mutex: &self.mutex }
+    }
+
+    /// Acquire a [`CanProxy`] for this bus.
+    pub fn acquire_can(&self) -> CanProxy<'_, M> {
+        CanProxy { mutex: &self.mutex }
+    }
+
+    /// Acquire an [`SpiDeviceProxy`] for this bus, managing the given chip-select pin.
+    ///
+    /// Unlike [`acquire_spi`][BusManagerSimple::acquire_spi], the returned proxy asserts and
+    /// deasserts `cs` itself as part of each transaction, so it is `Send` whenever the mutex is
+    /// `Sync` and the pin is `Send` (true for every built-in mutex except [`NullMutex`] when the
+    /// bus is `Send`), and can be used with any `BusManager`, not just [`BusManagerSimple`].
+    pub fn acquire_spi_device<CS>(&self, cs: CS) -> SpiDeviceProxy<'_, M, CS> {
+        SpiDeviceProxy {
+            mutex: &self.mutex,
+            cs,
+        }
+    }
+}
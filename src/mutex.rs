@@ -0,0 +1,42 @@
+/// A mutex that protects access to a shared bus.
+///
+/// Implementing this trait for a custom type allows it to be used for bus-sharing instead of one
+/// of the built-in mutex types.  A `BusMutex` is expected to provide exclusive access to the bus
+/// for the duration of the closure passed to [`lock()`][BusMutex::lock]; it is not expected to
+/// ever fail to acquire the lock (built-in implementations either rely on there being only a
+/// single execution context, or block until the lock is free).
+pub trait BusMutex {
+    /// The underlying bus type protected by this mutex.
+    type Bus;
+
+    /// Lock the mutex and run `f` with exclusive, mutable access to the bus.
+    fn lock<R>(&self, f: impl FnOnce(&mut Self::Bus) -> R) -> R;
+}
+
+/// A mutex based on a `RefCell`.
+///
+/// This mutex does not actually provide concurrency protection.  Instead, it relies on the
+/// guarantee that [`BusManagerSimple`][crate::BusManagerSimple] and the proxies it hands out are
+/// only ever used from a single execution context; any attempt to lock it recursively panics via
+/// `RefCell`'s own borrow checking.
+pub struct NullMutex<BUS> {
+    bus: core::cell::RefCell<BUS>,
+}
+
+impl<BUS> NullMutex<BUS> {
+    /// Create a new `NullMutex`, wrapping the passed bus.
+    pub fn create(bus: BUS) -> Self {
+        NullMutex {
+            bus: core::cell::RefCell::new(bus),
+        }
+    }
+}
+
+impl<BUS> BusMutex for NullMutex<BUS> {
+    type Bus = BUS;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut BUS) -> R) -> R {
+        let mut bus = self.bus.borrow_mut();
+        f(&mut bus)
+    }
+}
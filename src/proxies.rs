@@ -2,6 +2,7 @@ use embedded_hal::adc;
 use embedded_hal::blocking::i2c;
 use embedded_hal::blocking::spi;
 use embedded_hal::blocking::can;
+use embedded_hal::digital::v2 as digital;
 
 /// Proxy type for I2C bus sharing.
 ///
@@ -62,6 +63,114 @@ where
     }
 }
 
+/// A single step of an [`I2cProxy::transaction`].
+pub enum Operation<'a> {
+    /// Read into the given buffer.
+    Read(&'a mut [u8]),
+    /// Write the given buffer.
+    Write(&'a [u8]),
+}
+
+impl<'a, M: crate::BusMutex> I2cProxy<'a, M>
+where
+    M::Bus: i2c::Write + i2c::Read,
+{
+    /// Run a sequence of read/write [`Operation`]s against `addr` under one held lock.
+    ///
+    /// The whole sequence is executed inside one `mutex.lock()`, so no other proxy can interleave
+    /// an access in the middle of it.  Note that this only makes the sequence atomic with respect
+    /// to *other proxies*, not with respect to bus framing: each `Operation` is still dispatched
+    /// as its own standalone `i2c::Write`/`i2c::Read` call, so a `Write` followed by a `Read`
+    /// still has a STOP between them rather than a repeated-START.  Devices that require a
+    /// repeated-START to keep a register pointer selected across a write-then-read (rather than
+    /// just freedom from preemption) need [`i2c::WriteRead`] instead.  Execution stops at the
+    /// first error.
+    #[allow(clippy::type_complexity)]
+    pub fn transaction(
+        &mut self,
+        addr: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), I2cTransactionError<<M::Bus as i2c::Write>::Error, <M::Bus as i2c::Read>::Error>>
+    {
+        self.mutex.lock(|bus| {
+            for operation in operations {
+                match operation {
+                    Operation::Write(buffer) => i2c::Write::write(bus, addr, buffer)
+                        .map_err(I2cTransactionError::Write)?,
+                    Operation::Read(buffer) => i2c::Read::read(bus, addr, buffer)
+                        .map_err(I2cTransactionError::Read)?,
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Error type for [`I2cProxy::transaction`].
+#[derive(Debug)]
+pub enum I2cTransactionError<W, R> {
+    /// A [`Operation::Write`] step failed.
+    Write(W),
+    /// A [`Operation::Read`] step failed.
+    Read(R),
+}
+
+impl<'a, BUS> Clone for I2cProxy<'a, crate::AtomicMutex<BUS>> {
+    fn clone(&self) -> Self {
+        Self { mutex: &self.mutex }
+    }
+}
+
+impl<'a, BUS> i2c::Write for I2cProxy<'a, crate::AtomicMutex<BUS>>
+where
+    BUS: i2c::Write,
+{
+    type Error = crate::AtomicError<BUS::Error>;
+
+    fn write(&mut self, addr: u8, buffer: &[u8]) -> Result<(), Self::Error> {
+        match self.mutex.try_lock(|bus| bus.write(addr, buffer)) {
+            Ok(result) => result.map_err(crate::AtomicError::Other),
+            Err(crate::Busy) => Err(crate::AtomicError::Busy),
+        }
+    }
+}
+
+impl<'a, BUS> i2c::Read for I2cProxy<'a, crate::AtomicMutex<BUS>>
+where
+    BUS: i2c::Read,
+{
+    type Error = crate::AtomicError<BUS::Error>;
+
+    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        match self.mutex.try_lock(|bus| bus.read(addr, buffer)) {
+            Ok(result) => result.map_err(crate::AtomicError::Other),
+            Err(crate::Busy) => Err(crate::AtomicError::Busy),
+        }
+    }
+}
+
+impl<'a, BUS> i2c::WriteRead for I2cProxy<'a, crate::AtomicMutex<BUS>>
+where
+    BUS: i2c::WriteRead,
+{
+    type Error = crate::AtomicError<BUS::Error>;
+
+    fn write_read(
+        &mut self,
+        addr: u8,
+        buffer_in: &[u8],
+        buffer_out: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        match self
+            .mutex
+            .try_lock(|bus| bus.write_read(addr, buffer_in, buffer_out))
+        {
+            Ok(result) => result.map_err(crate::AtomicError::Other),
+            Err(crate::Busy) => Err(crate::AtomicError::Busy),
+        }
+    }
+}
+
 /// Proxy type for SPI bus sharing.
 ///
 /// The `SpiProxy` implements all (blocking) SPI traits so it can be passed to drivers instead of
@@ -71,6 +180,8 @@ where
 /// is due to drivers usually managing the chip-select pin manually which would be inherently racy
 /// in a concurrent environment (because the mutex is locked only after asserting CS).  To ensure
 /// safe usage, a `SpiProxy` can only be created when using [`BusManagerSimple`] and is `!Send`.
+/// To share a SPI bus across tasks or threads, use [`SpiDeviceProxy`] instead, which manages the
+/// chip-select pin itself.
 ///
 /// [acquire_spi]: ./struct.BusManager.html#method.acquire_spi
 /// [`BusManagerSimple`]: ./type.BusManagerSimple.html
@@ -111,6 +222,153 @@ where
     }
 }
 
+/// Error type for [`SpiDeviceProxy`] operations.
+///
+/// A transaction can fail either because the underlying bus returned an error, or because
+/// asserting/deasserting the chip-select pin did.
+#[derive(Debug)]
+pub enum SpiDeviceError<BUS, CS> {
+    /// An error occurred on the underlying bus.
+    Spi(BUS),
+    /// An error occurred toggling the chip-select pin.
+    Cs(CS),
+}
+
+#[cfg(feature = "async")]
+impl<BUS, CS> embedded_hal_async::spi::Error for SpiDeviceError<BUS, CS>
+where
+    BUS: embedded_hal_async::spi::Error,
+    CS: core::fmt::Debug,
+{
+    fn kind(&self) -> embedded_hal_async::spi::ErrorKind {
+        match self {
+            SpiDeviceError::Spi(e) => e.kind(),
+            SpiDeviceError::Cs(_) => embedded_hal_async::spi::ErrorKind::ChipSelectFault,
+        }
+    }
+}
+
+/// Proxy type for SPI bus sharing that manages its own chip-select pin.
+///
+/// Unlike [`SpiProxy`], a `SpiDeviceProxy` owns the chip-select pin and asserts/deasserts it
+/// itself as part of a single locked transaction, instead of relying on the driver to toggle CS
+/// outside the lock.  Because CS is no longer racy with respect to other proxy users, this type
+/// is `Send` whenever the mutex is `Sync` and the pin is `Send`, and can be used with
+/// [`BusManagerStd`] or [`BusManagerCortexM`], not just [`BusManagerSimple`].
+///
+/// A `SpiDeviceProxy` is created by calling
+/// [`BusManager::acquire_spi_device()`][acquire_spi_device].
+///
+/// This mirrors how RTIC's `ArbiterDevice` and `embedded-hal-bus`'s `SpiDevice` bundle
+/// chip-select management into the locked region to make concurrent sharing safe.
+///
+/// [acquire_spi_device]: ./struct.BusManager.html#method.acquire_spi_device
+/// [`BusManagerStd`]: ./type.BusManagerStd.html
+/// [`BusManagerCortexM`]: ./type.BusManagerCortexM.html
+/// [`BusManagerSimple`]: ./type.BusManagerSimple.html
+#[derive(Debug)]
+pub struct SpiDeviceProxy<'a, M, CS> {
+    pub(crate) mutex: &'a M,
+    pub(crate) cs: CS,
+}
+
+impl<'a, M, CS> SpiDeviceProxy<'a, M, CS>
+where
+    M: crate::BusMutex,
+    CS: digital::OutputPin,
+{
+    /// Run `f` as a single SPI transaction: assert CS, run `f` against the locked bus, then
+    /// deassert CS, all while holding the bus mutex so no other proxy can interleave.
+    pub fn transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut M::Bus) -> Result<R, E>,
+    ) -> Result<R, SpiDeviceError<E, CS::Error>> {
+        let cs = &mut self.cs;
+        self.mutex.lock(move |bus| {
+            cs.set_low().map_err(SpiDeviceError::Cs)?;
+            let result = f(bus).map_err(SpiDeviceError::Spi);
+            cs.set_high().map_err(SpiDeviceError::Cs)?;
+            result
+        })
+    }
+}
+
+impl<'a, M, CS> spi::Transfer<u8> for SpiDeviceProxy<'a, M, CS>
+where
+    M: crate::BusMutex,
+    M::Bus: spi::Transfer<u8>,
+    CS: digital::OutputPin,
+{
+    type Error = SpiDeviceError<<M::Bus as spi::Transfer<u8>>::Error, CS::Error>;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+        self.transaction(move |bus| bus.transfer(words))
+    }
+}
+
+impl<'a, M, CS> spi::Write<u8> for SpiDeviceProxy<'a, M, CS>
+where
+    M: crate::BusMutex,
+    M::Bus: spi::Write<u8>,
+    CS: digital::OutputPin,
+{
+    type Error = SpiDeviceError<<M::Bus as spi::Write<u8>>::Error, CS::Error>;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.transaction(|bus| bus.write(words))
+    }
+}
+
+impl<'a, BUS, CS> SpiDeviceProxy<'a, crate::AtomicMutex<BUS>, CS>
+where
+    CS: digital::OutputPin,
+{
+    /// Run `f` as a single, non-blocking SPI transaction: assert CS, run `f` against the locked
+    /// bus, then deassert CS, all while holding the bus mutex.
+    ///
+    /// Returns [`AtomicError::Busy`][crate::AtomicError::Busy] instead of blocking if the bus is
+    /// already locked by another accessor (e.g. an interrupt handler), without touching CS.
+    pub fn transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut BUS) -> Result<R, E>,
+    ) -> Result<R, crate::AtomicError<SpiDeviceError<E, CS::Error>>> {
+        let cs = &mut self.cs;
+        match self.mutex.try_lock(move |bus| {
+            cs.set_low().map_err(SpiDeviceError::Cs)?;
+            let result = f(bus).map_err(SpiDeviceError::Spi);
+            cs.set_high().map_err(SpiDeviceError::Cs)?;
+            result
+        }) {
+            Ok(result) => result.map_err(crate::AtomicError::Other),
+            Err(crate::Busy) => Err(crate::AtomicError::Busy),
+        }
+    }
+}
+
+impl<'a, BUS, CS> spi::Transfer<u8> for SpiDeviceProxy<'a, crate::AtomicMutex<BUS>, CS>
+where
+    BUS: spi::Transfer<u8>,
+    CS: digital::OutputPin,
+{
+    type Error = crate::AtomicError<SpiDeviceError<BUS::Error, CS::Error>>;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+        self.transaction(move |bus| bus.transfer(words))
+    }
+}
+
+impl<'a, BUS, CS> spi::Write<u8> for SpiDeviceProxy<'a, crate::AtomicMutex<BUS>, CS>
+where
+    BUS: spi::Write<u8>,
+    CS: digital::OutputPin,
+{
+    type Error = crate::AtomicError<SpiDeviceError<BUS::Error, CS::Error>>;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.transaction(|bus| bus.write(words))
+    }
+}
+
 /// Proxy type for ADC sharing.
 ///
 /// The `AdcProxy` implements OneShot trait so it can be passed to drivers instead of
@@ -188,4 +446,156 @@ where
     }
 
     type Frame = <M::Bus as embedded_hal::blocking::can::Can>::Frame;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::blocking::spi::Transfer as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    #[derive(Clone)]
+    struct FakeCs {
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl digital::OutputPin for FakeCs {
+        type Error = core::convert::Infallible;
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.log.borrow_mut().push("cs_low");
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.log.borrow_mut().push("cs_high");
+            Ok(())
+        }
+    }
+
+    struct FakeSpiBus {
+        log: Rc<RefCell<Vec<&'static str>>>,
+        fail: bool,
+    }
+
+    impl spi::Transfer<u8> for FakeSpiBus {
+        type Error = &'static str;
+
+        fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+            self.log.borrow_mut().push("transfer");
+            if self.fail {
+                Err("bus error")
+            } else {
+                Ok(words)
+            }
+        }
+    }
+
+    #[test]
+    fn spi_device_proxy_asserts_cs_before_and_deasserts_after_transfer() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mutex = crate::NullMutex::create(FakeSpiBus {
+            log: log.clone(),
+            fail: false,
+        });
+        let mut proxy = SpiDeviceProxy {
+            mutex: &mutex,
+            cs: FakeCs { log: log.clone() },
+        };
+
+        proxy.transfer(&mut [0u8]).unwrap();
+
+        assert_eq!(*log.borrow(), vec!["cs_low", "transfer", "cs_high"]);
+    }
+
+    #[test]
+    fn spi_device_proxy_deasserts_cs_even_when_the_bus_errors() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mutex = crate::NullMutex::create(FakeSpiBus {
+            log: log.clone(),
+            fail: true,
+        });
+        let mut proxy = SpiDeviceProxy {
+            mutex: &mutex,
+            cs: FakeCs { log: log.clone() },
+        };
+
+        let err = proxy.transfer(&mut [0u8]).unwrap_err();
+
+        assert!(matches!(err, SpiDeviceError::Spi("bus error")));
+        assert_eq!(*log.borrow(), vec!["cs_low", "transfer", "cs_high"]);
+    }
+
+    struct FakeI2cBus {
+        log: Rc<RefCell<Vec<&'static str>>>,
+        fail_on_read: bool,
+    }
+
+    impl i2c::Write for FakeI2cBus {
+        type Error = &'static str;
+
+        fn write(&mut self, _addr: u8, _buffer: &[u8]) -> Result<(), Self::Error> {
+            self.log.borrow_mut().push("write");
+            Ok(())
+        }
+    }
+
+    impl i2c::Read for FakeI2cBus {
+        type Error = &'static str;
+
+        fn read(&mut self, _addr: u8, _buffer: &mut [u8]) -> Result<(), Self::Error> {
+            self.log.borrow_mut().push("read");
+            if self.fail_on_read {
+                Err("read failed")
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn i2c_proxy_transaction_runs_operations_in_sequence() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mutex = crate::NullMutex::create(FakeI2cBus {
+            log: log.clone(),
+            fail_on_read: false,
+        });
+        let mut proxy = I2cProxy { mutex: &mutex };
+        let mut read_buf = [0u8; 1];
+        let write_buf = [1u8];
+
+        proxy
+            .transaction(
+                0x42,
+                &mut [Operation::Write(&write_buf), Operation::Read(&mut read_buf)],
+            )
+            .unwrap();
+
+        assert_eq!(*log.borrow(), vec!["write", "read"]);
+    }
+
+    #[test]
+    fn i2c_proxy_transaction_stops_at_first_error() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mutex = crate::NullMutex::create(FakeI2cBus {
+            log: log.clone(),
+            fail_on_read: true,
+        });
+        let mut proxy = I2cProxy { mutex: &mutex };
+        let mut read_buf = [0u8; 1];
+        let write_buf = [1u8];
+
+        let err = proxy
+            .transaction(
+                0x42,
+                &mut [Operation::Read(&mut read_buf), Operation::Write(&write_buf)],
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, I2cTransactionError::Read("read failed")));
+        // The `Write` after the failing `Read` must never execute.
+        assert_eq!(*log.borrow(), vec!["read"]);
+    }
 }
\ No newline at end of file
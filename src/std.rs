@@ -0,0 +1,41 @@
+//! `std`-based mutex, for sharing a bus across threads on platforms with `std` available.
+
+/// A mutex based on a [`std::sync::Mutex`].
+///
+/// This mutex can safely be used for sharing a bus across multiple threads.  Locking blocks the
+/// calling thread until the bus becomes available.
+pub struct StdMutex<BUS> {
+    bus: std::sync::Mutex<BUS>,
+}
+
+impl<BUS> StdMutex<BUS> {
+    /// Create a new `StdMutex`, wrapping the passed bus.
+    pub fn create(bus: BUS) -> Self {
+        StdMutex {
+            bus: std::sync::Mutex::new(bus),
+        }
+    }
+}
+
+impl<BUS> crate::BusMutex for StdMutex<BUS> {
+    type Bus = BUS;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut BUS) -> R) -> R {
+        let mut bus = self.bus.lock().unwrap();
+        f(&mut bus)
+    }
+}
+
+/// [`BusManager`][crate::BusManager] that uses a [`StdMutex`] for synchronization.
+///
+/// This manager can be shared between threads (via an `Arc`, for example).
+pub type BusManagerStd<BUS> = crate::BusManager<StdMutex<BUS>>;
+
+impl<BUS> BusManagerStd<BUS> {
+    /// Create a new `BusManagerStd`, wrapping the passed bus.
+    pub fn new(bus: BUS) -> Self {
+        crate::BusManager {
+            mutex: StdMutex::create(bus),
+        }
+    }
+}